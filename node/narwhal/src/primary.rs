@@ -13,10 +13,12 @@
 // limitations under the License.
 
 use crate::{
-    helpers::{assign_to_worker, init_worker_channels, Batch, PrimaryReceiver, PrimarySender},
+    helpers::{assign_to_worker, init_worker_channels, Batch, BatchCertificate, PrimaryReceiver, PrimarySender},
     BatchPropose,
     BatchSealed,
     BatchSignature,
+    CertificateRequest,
+    CertificateResponse,
     Event,
     Gateway,
     Shared,
@@ -30,13 +32,32 @@ use snarkvm::{
     prelude::{
         block::Transaction,
         coinbase::{ProverSolution, PuzzleCommitment},
+        Address,
+        Field,
         Signature,
     },
 };
 
 use parking_lot::{Mutex, RwLock};
-use std::{collections::HashMap, future::Future, net::SocketAddr, sync::Arc};
-use tokio::task::JoinHandle;
+use std::{
+    collections::HashMap,
+    future::Future,
+    net::SocketAddr,
+    sync::Arc,
+    time::{SystemTime, UNIX_EPOCH},
+};
+use tokio::{sync::oneshot, task::JoinHandle};
+
+/// The minimum amount of time to wait, in seconds, before proposing a new batch.
+///
+/// This prevents runaway proposal spam that wastes bandwidth when transmissions are empty.
+const MIN_BATCH_DELAY_IN_SECS: i64 = 1;
+/// The maximum amount of time to wait, in milliseconds, before proposing a new batch, even
+/// under light load.
+const MAX_BATCH_DELAY_IN_MS: u64 = 5000;
+/// The maximum amount of time to wait, in milliseconds, for a peer to respond to a certificate
+/// request before giving up.
+const MAX_FETCH_TIMEOUT_IN_MS: u64 = 5000;
 
 #[derive(Clone)]
 pub struct Primary<N: Network> {
@@ -46,8 +67,21 @@ pub struct Primary<N: Network> {
     gateway: Gateway<N>,
     /// The workers.
     workers: Arc<RwLock<Vec<Worker<N>>>>,
-    /// The currently-proposed batch, along with its signatures.
-    proposed_batch: Arc<RwLock<Option<(Batch<N>, Vec<Signature<N>>)>>>,
+    /// The currently-proposed batch, along with the signatures received for it, keyed by signer address.
+    proposed_batch: Arc<RwLock<Option<(Batch<N>, HashMap<Address<N>, Signature<N>>)>>>,
+    /// The timestamp, in seconds, at which the last batch was proposed.
+    latest_proposed_batch_timestamp: Arc<RwLock<i64>>,
+    /// The pending certificate requests, awaiting a response from the peer that was asked, keyed
+    /// by the peer that was asked and the certificate ID requested. Keying by peer (rather than
+    /// just certificate ID) lets two different peers' concurrent requests for the same
+    /// certificate coexist, instead of the second silently dropping the first's callback.
+    certificate_requests: Arc<RwLock<HashMap<(SocketAddr, Field<N>), oneshot::Sender<BatchCertificate<N>>>>>,
+    /// The round and batch ID we have reserved (or signed) for each proposer address, to guard
+    /// against signing two different batches in the same round. The signature is `None` from
+    /// the moment the slot is reserved until signing completes, so that reservation and signing
+    /// are decoupled: two concurrent batch proposes from the same proposer can't both observe
+    /// an empty cache and both proceed to sign.
+    signed_proposals: Arc<RwLock<HashMap<Address<N>, (u64, Field<N>, Option<Signature<N>>)>>>,
     /// The spawned handles.
     handles: Arc<Mutex<Vec<JoinHandle<()>>>>,
 }
@@ -63,6 +97,11 @@ impl<N: Network> Primary<N> {
             gateway,
             workers: Default::default(),
             proposed_batch: Default::default(),
+            // Seed this to the current time, not 0, so that the minimum batch-proposal delay
+            // is actually honored for the first batch a primary proposes after starting up.
+            latest_proposed_batch_timestamp: Arc::new(RwLock::new(now())),
+            certificate_requests: Default::default(),
+            signed_proposals: Default::default(),
             handles: Default::default(),
         })
     }
@@ -119,10 +158,7 @@ impl<N: Network> Primary<N> {
     /// 2. Sign the batch.
     /// 3. Set the batch in the primary.
     /// 4. Broadcast the batch to all validators for signing.
-    pub fn propose_batch(&self) -> Result<()> {
-        // Initialize the RNG.
-        let mut rng = rand::thread_rng();
-
+    pub async fn propose_batch(&self) -> Result<()> {
         // Initialize a map of the transmissions.
         let mut transmissions = HashMap::new();
         // Drain the workers.
@@ -136,19 +172,76 @@ impl<N: Network> Primary<N> {
         let round = self.shared.round();
         // Retrieve the previous certificates.
         let previous_certificates = self.shared.previous_certificates(round).unwrap_or_default();
-        // Sign the batch.
-        let batch =
-            Batch::new(self.gateway.account().private_key(), round, transmissions, previous_certificates, &mut rng)?;
+        // Retrieve the private key.
+        let private_key = *self.gateway.account().private_key();
+        // Sign the batch. This is an elliptic curve operation, so it is moved onto a blocking
+        // task to avoid stalling the async reactor.
+        let batch = tokio::task::spawn_blocking(move || {
+            Batch::new(&private_key, round, transmissions, previous_certificates, &mut rand::thread_rng())
+        })
+        .await??;
 
         // Set the proposed batch.
-        *self.proposed_batch.write() = Some((batch.clone(), vec![]));
+        *self.proposed_batch.write() = Some((batch.clone(), HashMap::new()));
 
         // Broadcast the batch to all validators for signing.
         self.gateway.broadcast(Event::BatchPropose(BatchPropose::new(Data::Object(batch))));
+        // Update the timestamp of the last proposed batch.
+        *self.latest_proposed_batch_timestamp.write() = now();
         Ok(())
     }
 }
 
+/// Returns the current Unix timestamp, in seconds.
+fn now() -> i64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).expect("current time is before the Unix epoch").as_secs() as i64
+}
+
+/// The outcome of attempting to reserve the right to sign a proposer's batch for a round.
+enum SignedProposalOutcome<N: Network> {
+    /// We reserved the slot; proceed to verify and sign this batch.
+    Proceed,
+    /// We already signed this exact batch; resend our cached signature instead of re-signing.
+    Resend(Signature<N>),
+    /// Another concurrent task already reserved this exact batch and is still signing it.
+    InFlight,
+    /// We already reserved or signed a different batch from this proposer in this round; refuse.
+    Refuse,
+}
+
+/// Atomically checks the signed-proposals cache for `(address, round)` and, unless the
+/// proposer has already claimed a *different* batch this round, reserves `batch_id` (with no
+/// signature yet) before returning.
+///
+/// This must be called while holding `signed_proposals`'s write lock for the entire
+/// check-then-insert, so that two concurrent batch proposes from the same proposer in the same
+/// round cannot both observe an empty cache and both proceed to sign — that would defeat the
+/// entire purpose of this cache, which is to prevent us from signing two distinct batches from
+/// the same proposer in the same round.
+fn reserve_signed_proposal<N: Network>(
+    signed_proposals: &mut HashMap<Address<N>, (u64, Field<N>, Option<Signature<N>>)>,
+    address: Address<N>,
+    round: u64,
+    batch_id: Field<N>,
+) -> SignedProposalOutcome<N> {
+    if let Some((signed_round, signed_batch_id, signature)) = signed_proposals.get(&address) {
+        if *signed_round == round {
+            return if *signed_batch_id != batch_id {
+                SignedProposalOutcome::Refuse
+            } else {
+                match signature {
+                    Some(signature) => SignedProposalOutcome::Resend(signature.clone()),
+                    None => SignedProposalOutcome::InFlight,
+                }
+            };
+        }
+    }
+    // Prune entries from prior rounds to bound memory, then reserve this round's slot.
+    signed_proposals.retain(|_, (signed_round, _, _)| *signed_round >= round);
+    signed_proposals.insert(address, (round, batch_id, None));
+    SignedProposalOutcome::Proceed
+}
+
 impl<N: Network> Primary<N> {
     /// Starts the primary handlers.
     fn start_handlers(&self, receiver: PrimaryReceiver<N>) {
@@ -156,6 +249,8 @@ impl<N: Network> Primary<N> {
             mut rx_batch_propose,
             mut rx_batch_signature,
             mut rx_batch_sealed,
+            mut rx_certificate_request,
+            mut rx_certificate_response,
             mut rx_unconfirmed_solution,
             mut rx_unconfirmed_transaction,
         } = receiver;
@@ -166,12 +261,21 @@ impl<N: Network> Primary<N> {
         self.start_batch_sealer();
 
         // Process the proposed batch.
+        //
+        // Each batch propose is handled on its own spawned task, rather than awaited inline in
+        // this loop, because handling one can require fetching missing previous certificates
+        // from the proposer (up to `MAX_FETCH_TIMEOUT_IN_MS` per certificate). Awaiting that
+        // inline here would let a single slow or unresponsive proposer stall the processing of
+        // every other peer's batch proposes.
         let self_clone = self.clone();
         self.spawn(async move {
             while let Some((peer_ip, batch_propose)) = rx_batch_propose.recv().await {
-                if let Err(e) = self_clone.process_batch_propose_from_peer(peer_ip, batch_propose).await {
-                    error!("Failed to process a batch propose from peer '{peer_ip}': {e}");
-                }
+                let self_clone = self_clone.clone();
+                tokio::spawn(async move {
+                    if let Err(e) = self_clone.process_batch_propose_from_peer(peer_ip, batch_propose).await {
+                        error!("Failed to process a batch propose from peer '{peer_ip}': {e}");
+                    }
+                });
             }
         });
 
@@ -196,6 +300,46 @@ impl<N: Network> Primary<N> {
                 };
                 // Store the sealed batch in the shared state.
                 self_clone.shared.store_sealed_batch(peer_ip, batch_certificate);
+                // Other validators' certificates can complete a round's quorum on their own,
+                // even if this primary's own batch is slow to collect signatures (or never
+                // does); check after every insertion, not only after a local seal.
+                self_clone.try_advance_round();
+            }
+        });
+
+        // Process certificate requests from peers that are missing a certificate we have.
+        let self_clone = self.clone();
+        self.spawn(async move {
+            while let Some((peer_ip, certificate_request)) = rx_certificate_request.recv().await {
+                // Look up the requested certificate in the shared state.
+                let Some(certificate) = self_clone.shared.get_certificate(&certificate_request.certificate_id) else {
+                    warn!("Missing certificate '{}' requested by peer '{peer_ip}'", certificate_request.certificate_id);
+                    continue;
+                };
+                // Send the certificate back to the requesting peer.
+                let response = CertificateResponse::new(Data::Object(certificate));
+                self_clone.gateway.send(peer_ip, Event::CertificateResponse(response));
+            }
+        });
+
+        // Process certificate responses to our own pending requests.
+        let self_clone = self.clone();
+        self.spawn(async move {
+            while let Some((peer_ip, certificate_response)) = rx_certificate_response.recv().await {
+                // Deserialize the certificate.
+                let Ok(certificate) = certificate_response.certificate.deserialize().await else {
+                    error!("Failed to deserialize the certificate response from peer '{peer_ip}'");
+                    continue;
+                };
+                // Fulfill the pending request, if there is one waiting on this certificate from
+                // this peer.
+                if let Some(callback) =
+                    self_clone.certificate_requests.write().remove(&(peer_ip, certificate.certificate_id()))
+                {
+                    let _ = callback.send(certificate);
+                } else {
+                    warn!("Received an unsolicited certificate response from peer '{peer_ip}'");
+                }
             }
         });
 
@@ -241,19 +385,27 @@ impl<N: Network> Primary<N> {
         // Initialize the batch proposer.
         let self_clone = self.clone();
         self.spawn(async move {
-            // TODO: Implement proper timeouts to propose a batch. Need to sync the primaries.
-            // Sleep.
-            tokio::time::sleep(std::time::Duration::from_millis(5000)).await;
             loop {
-                // If there is a proposed batch, wait for it to be sealed.
+                // If there is a proposed batch, wait for the sealer to either seal it or expire
+                // it. The sealer is the sole owner of clearing `proposed_batch`, so that it
+                // can never race this task's own clearing with its `take()` on sealing.
                 if self_clone.proposed_batch.read().is_some() {
-                    // Sleep briefly, but longer than if there were no batch.
                     tokio::time::sleep(std::time::Duration::from_millis(1000)).await;
                     continue;
                 }
 
-                // If there is no proposed batch, propose one.
-                if let Err(e) = self_clone.propose_batch() {
+                // Compute the time elapsed, in milliseconds, since the last proposed batch.
+                let elapsed_ms = now().saturating_sub(*self_clone.latest_proposed_batch_timestamp.read()).max(0) as u64 * 1000;
+
+                // Refuse to propose a new batch until the minimum delay has elapsed, to avoid
+                // spamming the network when transmissions are empty.
+                if elapsed_ms < MIN_BATCH_DELAY_IN_SECS as u64 * 1000 {
+                    tokio::time::sleep(std::time::Duration::from_millis(1000)).await;
+                    continue;
+                }
+
+                // Propose a new batch.
+                if let Err(e) = self_clone.propose_batch().await {
                     error!("Failed to propose a batch: {e}");
                 }
             }
@@ -278,28 +430,38 @@ impl<N: Network> Primary<N> {
                 }
 
                 // If there is a batch, check if it is expired or ready to be sealed.
-                if let Some((batch, signatures)) = self_clone.proposed_batch.read().clone() {
-                    // TODO (howardwu): Use stake checks.
-                    // // If the batch is expired, clear it.
-                    // is_expired = batch.timestamp() + BATCH_EXPIRATION
-                    //     < SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs();
-                    // // If the batch is ready to be sealed, seal it.
-                    // is_ready = signatures.len() >= self_clone.shared.num_validators();
-                    if !signatures.is_empty() {
-                        is_ready = true;
-                    }
+                if let Some((_batch, signatures)) = self_clone.proposed_batch.read().clone() {
+                    // This task is the sole owner of clearing `proposed_batch`: the proposer
+                    // only ever waits on it, never clears it itself. That way a batch reaching
+                    // quorum right as it expires can't race a `take()` here against a
+                    // concurrent clear from elsewhere.
+                    let elapsed_ms =
+                        now().saturating_sub(*self_clone.latest_proposed_batch_timestamp.read()).max(0) as u64 * 1000;
+                    is_expired = elapsed_ms >= MAX_BATCH_DELAY_IN_MS;
+
+                    // If the accumulated stake of the signers, including our own, reaches the
+                    // 2f+1 availability threshold, the batch is ready to be sealed.
+                    let mut signers: Vec<_> = signatures.keys().copied().collect();
+                    signers.push(self_clone.gateway.account().address());
+                    is_ready = self_clone.shared.is_quorum_threshold_reached(&signers);
                 }
 
-                // If the batch is expired, clear it.
-                if is_expired {
+                // If the batch is expired and not also ready to be sealed, clear it.
+                if is_expired && !is_ready {
                     *self_clone.proposed_batch.write() = None;
                 }
                 // If the batch is ready to be sealed, seal it.
                 if is_ready {
-                    // Retrieve the batch and signatures, clearing the proposed batch.
-                    let (batch, signatures) = self_clone.proposed_batch.write().take().unwrap();
+                    // Retrieve the batch and signatures, clearing the proposed batch. This may
+                    // already be `None` if it was cleared concurrently; if so, there is nothing
+                    // left to seal.
+                    let Some((batch, signatures)) = self_clone.proposed_batch.write().take() else {
+                        continue;
+                    };
+                    // Retrieve the round, before the batch is consumed by sealing it.
+                    let round = batch.round();
                     // Seal the batch.
-                    let sealed_batch = batch.seal(signatures);
+                    let sealed_batch = batch.seal(signatures.into_values().collect());
                     // Fetch the certificate.
                     let certificate = sealed_batch.certificate().clone();
                     // Fetch the address.
@@ -311,8 +473,12 @@ impl<N: Network> Primary<N> {
                     let event = BatchSealed::new(Data::Object(certificate));
                     // Broadcast the sealed batch to all validators.
                     self_clone.gateway.broadcast(Event::BatchSealed(event));
-                    // TODO: Increment the round.
-                    info!("\n\n\n\nA batch has been sealed!\n\n\n");
+                    info!("A batch has been sealed for round '{round}'");
+
+                    // If a stake-weighted quorum of sealed certificates for the current round,
+                    // including our own, has now been reached, advance to the next round. This
+                    // also wakes the batch proposer, since `proposed_batch` is now `None`.
+                    self_clone.try_advance_round();
                 }
 
                 // Sleep briefly.
@@ -323,25 +489,153 @@ impl<N: Network> Primary<N> {
 
     /// Processes a batch propose from a peer.
     async fn process_batch_propose_from_peer(&self, peer_ip: SocketAddr, batch_propose: BatchPropose<N>) -> Result<()> {
-        // // Retrieve the current round.
-        // let round = self.shared.round();
+        // Retrieve the current round.
+        let round = self.shared.round();
         // Deserialize the batch.
         let batch = batch_propose.batch.deserialize().await?;
 
-        // TODO (howardwu): Verify the batch.
+        // Retrieve the address of the peer.
+        let Some(address) = self.shared.get_address(&peer_ip) else {
+            warn!("Received a batch propose from a disconnected peer '{peer_ip}'");
+            return Ok(());
+        };
+        // Ensure the peer is a current committee member.
+        if !self.shared.is_committee_member(&address) {
+            warn!("Received a batch propose from a non-committee peer '{peer_ip}'");
+            return Ok(());
+        }
+        // Ensure the batch is for the primary's current round, rejecting stale or future rounds.
+        if batch.round() != round {
+            warn!(
+                "Received a batch propose for round '{}' from peer '{peer_ip}', expected round '{round}'",
+                batch.round()
+            );
+            return Ok(());
+        }
+        // Verify the batch author's own signature over the batch ID.
+        if !batch.signature().verify(&address, &[batch.batch_id()]) {
+            warn!("Received a batch propose with an invalid signature from peer '{peer_ip}'");
+            return Ok(());
+        }
+        // Guard against equivocation: a proposer must not get our signature on two different
+        // batches in the same round. Reserving the slot (rather than just checking it) while
+        // holding the write lock for the whole check-then-insert is what actually prevents two
+        // concurrent batch proposes from this proposer racing past this point; this cheap check
+        // also runs before the (potentially expensive) previous-certificates fetch and quorum
+        // verification below, so that a proposer re-sending or equivocating on a batch we've
+        // already ruled on cannot force us to repeat that work.
+        let batch_id = batch.batch_id();
+        match reserve_signed_proposal(&mut self.signed_proposals.write(), address, round, batch_id) {
+            SignedProposalOutcome::Resend(our_signature) => {
+                // We already signed this exact batch; resend our cached signature idempotently.
+                self.gateway.send(peer_ip, Event::BatchSignature(BatchSignature::new(batch_id, our_signature)));
+                return Ok(());
+            }
+            SignedProposalOutcome::Refuse => {
+                warn!("Refusing to sign a second batch from peer '{peer_ip}' in round '{round}'");
+                return Ok(());
+            }
+            // A concurrent task is already verifying and signing this exact batch; nothing to
+            // do here.
+            SignedProposalOutcome::InFlight => return Ok(()),
+            SignedProposalOutcome::Proceed => {}
+        }
+        // Fetch any previous certificates this primary has not yet stored, so that the quorum
+        // check below has all of the certificates it needs to verify.
+        for certificate_id in batch.previous_certificates().iter().copied() {
+            if !self.shared.contains_certificate(&certificate_id) {
+                if let Err(e) = self.fetch_certificate_from_peer(peer_ip, certificate_id).await {
+                    warn!("Failed to fetch a missing certificate from peer '{peer_ip}': {e}");
+                    // Release our reservation, so a subsequent retry of this batch isn't stuck
+                    // seeing it as in-flight forever.
+                    self.release_signed_proposal_reservation(address, round, batch_id);
+                    return Ok(());
+                }
+            }
+        }
+        // Ensure the previous certificates form a valid quorum for the prior round. A proposal for
+        // round 0 has no prior round, so it must not reference any previous certificates.
+        let is_valid_quorum = match round.checked_sub(1) {
+            Some(previous_round) => self.shared.is_quorum_for_round(batch.previous_certificates(), previous_round),
+            None => batch.previous_certificates().is_empty(),
+        };
+        if !is_valid_quorum {
+            warn!("Received a batch propose with an invalid quorum of previous certificates from peer '{peer_ip}'");
+            self.release_signed_proposal_reservation(address, round, batch_id);
+            return Ok(());
+        }
 
         // Store the proposed batch in the shared state.
         self.shared.store_proposed_batch(peer_ip, batch.clone());
 
-        // Initialize an RNG.
-        let rng = &mut rand::thread_rng();
-        // Sign the batch ID.
-        let signature = self.gateway.account().sign(&[batch.batch_id()], rng)?;
+        // Retrieve the private key.
+        let private_key = *self.gateway.account().private_key();
+        // Sign the batch ID. This is an elliptic curve operation, so it is moved onto a
+        // blocking task to avoid stalling the async reactor.
+        let signature =
+            tokio::task::spawn_blocking(move || private_key.sign(&[batch_id], &mut rand::thread_rng())).await??;
+
+        // Fill in the signature for the reservation made above.
+        if let Some(entry) = self.signed_proposals.write().get_mut(&address) {
+            if entry.0 == round && entry.1 == batch_id {
+                entry.2 = Some(signature.clone());
+            }
+        }
+
         // Broadcast the signature back to the validator.
-        self.gateway.send(peer_ip, Event::BatchSignature(BatchSignature::new(batch.batch_id(), signature)));
+        self.gateway.send(peer_ip, Event::BatchSignature(BatchSignature::new(batch_id, signature)));
         Ok(())
     }
 
+    /// Releases a previously-made signing reservation for `(address, round, batch_id)`, if it
+    /// is still unsigned. Used to allow a retry after this primary fails to verify a batch it
+    /// had reserved the right to sign (e.g. a missing certificate it could not fetch).
+    fn release_signed_proposal_reservation(&self, address: Address<N>, round: u64, batch_id: Field<N>) {
+        let mut signed_proposals = self.signed_proposals.write();
+        if matches!(signed_proposals.get(&address), Some((r, id, None)) if *r == round && *id == batch_id) {
+            signed_proposals.remove(&address);
+        }
+    }
+
+    /// Requests the given certificate from the specified peer, and waits (up to
+    /// `MAX_FETCH_TIMEOUT_IN_MS`) for the peer to respond with it. A response is accepted only
+    /// if its certificate ID matches the requested one; the accepted certificate is stored in
+    /// the shared state without checking its signatures.
+    async fn fetch_certificate_from_peer(&self, peer_ip: SocketAddr, certificate_id: Field<N>) -> Result<()> {
+        // Register a callback for the response, and send the request to the peer. Keyed by
+        // (peer_ip, certificate_id), not just certificate_id, so that two different peers'
+        // batches concurrently referencing the same missing certificate get independent
+        // requests instead of one silently dropping the other's callback.
+        let (callback_sender, callback_receiver) = oneshot::channel();
+        self.certificate_requests.write().insert((peer_ip, certificate_id), callback_sender);
+        self.gateway.send(peer_ip, Event::CertificateRequest(CertificateRequest::new(certificate_id)));
+
+        // Wait for the response, up to the maximum fetch timeout.
+        let result = tokio::time::timeout(std::time::Duration::from_millis(MAX_FETCH_TIMEOUT_IN_MS), callback_receiver).await;
+        // Remove the callback, in case it is still pending (e.g. on a timeout).
+        self.certificate_requests.write().remove(&(peer_ip, certificate_id));
+
+        match result {
+            Ok(Ok(certificate)) => {
+                self.shared.store_sealed_batch(peer_ip, certificate);
+                // Other validators' certificates can complete a round's quorum on their own;
+                // check after every insertion, not only after a local seal.
+                self.try_advance_round();
+                Ok(())
+            }
+            Ok(Err(_)) => bail!("The certificate response channel for '{certificate_id}' was dropped"),
+            Err(_) => bail!("Timed out waiting for certificate '{certificate_id}' from peer '{peer_ip}'"),
+        }
+    }
+
+    /// Advances to the next round if a stake-weighted quorum of sealed certificates for the
+    /// current round has now been reached, logging the transition.
+    fn try_advance_round(&self) {
+        if let Some(next_round) = self.shared.advance_round_if_ready() {
+            info!("Advanced to round '{next_round}'");
+        }
+    }
+
     /// Processes a batch signature from a peer.
     async fn process_batch_signature_from_peer(
         &self,
@@ -366,16 +660,26 @@ impl<N: Network> Primary<N> {
             warn!("Received a batch signature from a non-committee peer '{peer_ip}'");
             return Ok(());
         }
-        // Verify the signature.
-        if !signature.verify(&address, &[batch_id]) {
+        // Verify the signature. This is an elliptic curve operation, so it is moved onto a
+        // blocking task to avoid stalling the async reactor.
+        let signature_to_verify = signature.clone();
+        let address_to_verify = address;
+        let is_valid =
+            tokio::task::spawn_blocking(move || signature_to_verify.verify(&address_to_verify, &[batch_id])).await?;
+        if !is_valid {
             warn!("Received an invalid batch signature from peer '{peer_ip}'");
             return Ok(());
         }
 
-        // Add the signature to the batch.
+        // Add the signature to the batch, rejecting it if this signer is already counted
+        // (a single validator must not be able to inflate the quorum).
         if let Some((_, signatures)) = self.proposed_batch.write().as_mut() {
+            if signatures.contains_key(&address) {
+                warn!("Received a duplicate batch signature from peer '{peer_ip}'");
+                return Ok(());
+            }
             info!("Added a batch signature from peer '{peer_ip}'");
-            signatures.push(signature);
+            signatures.insert(address, signature);
         }
         Ok(())
     }
@@ -399,3 +703,77 @@ impl<N: Network> Primary<N> {
         self.gateway.shut_down().await;
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use snarkos_account::Account;
+    use snarkvm::{console::network::Testnet3, utilities::TestRng};
+
+    type CurrentNetwork = Testnet3;
+
+    fn sample_signature(rng: &mut TestRng) -> (Address<CurrentNetwork>, Signature<CurrentNetwork>) {
+        let account = Account::<CurrentNetwork>::new(rng).unwrap();
+        let signature = account.private_key().sign(&[Field::zero()], rng).unwrap();
+        (account.address(), signature)
+    }
+
+    #[test]
+    fn refuses_a_second_distinct_batch_and_replays_the_cached_signature_for_a_repeat() {
+        let rng = &mut TestRng::default();
+        let (proposer, our_signature) = sample_signature(rng);
+        let round = 1;
+        let signed_batch_id = Field::<CurrentNetwork>::rand(rng);
+        let mut signed_proposals = HashMap::new();
+        signed_proposals.insert(proposer, (round, signed_batch_id, Some(our_signature.clone())));
+
+        // A repeat of the exact same batch ID resends our cached signature.
+        match reserve_signed_proposal(&mut signed_proposals, proposer, round, signed_batch_id) {
+            SignedProposalOutcome::Resend(signature) => assert_eq!(signature, our_signature),
+            _ => panic!("expected a resend of the cached signature"),
+        }
+
+        // A second, distinct batch ID from the same proposer in the same round is refused.
+        let other_batch_id = Field::<CurrentNetwork>::rand(rng);
+        assert!(matches!(
+            reserve_signed_proposal(&mut signed_proposals, proposer, round, other_batch_id),
+            SignedProposalOutcome::Refuse
+        ));
+
+        // A batch ID from a new round is reserved for signing.
+        assert!(matches!(
+            reserve_signed_proposal(&mut signed_proposals, proposer, round + 1, other_batch_id),
+            SignedProposalOutcome::Proceed
+        ));
+    }
+
+    #[test]
+    fn concurrent_reservation_of_the_same_batch_is_in_flight_not_sign_anew() {
+        let rng = &mut TestRng::default();
+        let (proposer, _) = sample_signature(rng);
+        let round = 1;
+        let batch_id = Field::<CurrentNetwork>::rand(rng);
+        let mut signed_proposals = HashMap::new();
+
+        // The first task to see this batch reserves it (no signature yet).
+        assert!(matches!(
+            reserve_signed_proposal(&mut signed_proposals, proposer, round, batch_id),
+            SignedProposalOutcome::Proceed
+        ));
+
+        // A second, concurrent task for the very same batch must not also proceed to sign —
+        // that would be exactly the equivocation-enabling race this cache exists to prevent.
+        assert!(matches!(
+            reserve_signed_proposal(&mut signed_proposals, proposer, round, batch_id),
+            SignedProposalOutcome::InFlight
+        ));
+
+        // A concurrent task for a different batch in the same round is refused, not allowed
+        // to race past the reservation either.
+        let other_batch_id = Field::<CurrentNetwork>::rand(rng);
+        assert!(matches!(
+            reserve_signed_proposal(&mut signed_proposals, proposer, round, other_batch_id),
+            SignedProposalOutcome::Refuse
+        ));
+    }
+}
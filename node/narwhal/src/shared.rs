@@ -0,0 +1,291 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::helpers::{Batch, BatchCertificate, PrimarySender, SealedBatch};
+use snarkvm::{
+    console::prelude::*,
+    prelude::{Address, Field},
+};
+
+use indexmap::{IndexMap, IndexSet};
+use parking_lot::RwLock;
+use std::{
+    collections::{HashMap, HashSet},
+    net::SocketAddr,
+    sync::Arc,
+};
+
+/// The shared state of the memory pool, common to the primary and its workers.
+///
+/// `Shared` owns the committee's stake table, the store of sealed batch certificates, and the
+/// current round, and is the sole authority on whether a given set of signers or certificates
+/// forms a valid `2f+1` stake-weighted quorum.
+#[derive(Clone)]
+pub struct Shared<N: Network> {
+    /// The current round.
+    round: Arc<RwLock<u64>>,
+    /// The stake, in microcredits, of each committee member.
+    committee: Arc<RwLock<IndexMap<Address<N>, u64>>>,
+    /// The address bound to each connected peer.
+    addresses: Arc<RwLock<HashMap<SocketAddr, Address<N>>>>,
+    /// The proposed batch received from each peer, awaiting a quorum of signatures.
+    proposed_batches: Arc<RwLock<HashMap<SocketAddr, Batch<N>>>>,
+    /// Every sealed batch certificate this primary has stored, keyed by certificate ID.
+    certificates: Arc<RwLock<IndexMap<Field<N>, BatchCertificate<N>>>>,
+    /// The author of every certificate sealed so far for the current round, keyed by
+    /// certificate ID. Cleared each time the round advances.
+    current_round_certificates: Arc<RwLock<IndexMap<Field<N>, Address<N>>>>,
+    /// The certificate IDs to embed as `previous_certificates`, keyed by the round whose
+    /// proposal they are for.
+    previous_certificates: Arc<RwLock<HashMap<u64, IndexSet<Field<N>>>>>,
+    /// The primary's outbound sender, set once `Primary::run` starts.
+    primary_sender: Arc<RwLock<Option<PrimarySender<N>>>>,
+}
+
+impl<N: Network> Shared<N> {
+    /// Initializes a new instance of the shared state, for the given committee.
+    pub fn new(committee: IndexMap<Address<N>, u64>) -> Self {
+        Self {
+            round: Arc::new(RwLock::new(0)),
+            committee: Arc::new(RwLock::new(committee)),
+            addresses: Default::default(),
+            proposed_batches: Default::default(),
+            certificates: Default::default(),
+            current_round_certificates: Default::default(),
+            previous_certificates: Default::default(),
+            primary_sender: Default::default(),
+        }
+    }
+
+    /// Sets the primary sender.
+    pub fn set_primary_sender(&self, primary_sender: PrimarySender<N>) {
+        *self.primary_sender.write() = Some(primary_sender);
+    }
+
+    /// Returns the current round.
+    pub fn round(&self) -> u64 {
+        *self.round.read()
+    }
+
+    /// Returns the certificate IDs to embed as `previous_certificates` for a proposal in the
+    /// given round, if any have been snapshotted for it yet.
+    pub fn previous_certificates(&self, round: u64) -> Option<IndexSet<Field<N>>> {
+        self.previous_certificates.read().get(&round).cloned()
+    }
+
+    /// Returns the address bound to the given peer IP, if connected.
+    pub fn get_address(&self, peer_ip: &SocketAddr) -> Option<Address<N>> {
+        self.addresses.read().get(peer_ip).copied()
+    }
+
+    /// Returns `true` if the given address is a member of the committee.
+    pub fn is_committee_member(&self, address: &Address<N>) -> bool {
+        self.committee.read().contains_key(address)
+    }
+
+    /// Returns the stake, in microcredits, of the given address, or `0` if it is not a member
+    /// of the committee.
+    pub fn get_stake(&self, address: &Address<N>) -> u64 {
+        self.committee.read().get(address).copied().unwrap_or(0)
+    }
+
+    /// Stores the given proposed batch, received from the given peer.
+    pub fn store_proposed_batch(&self, peer_ip: SocketAddr, batch: Batch<N>) {
+        self.proposed_batches.write().insert(peer_ip, batch);
+    }
+
+    /// Returns `true` if the given certificate ID has already been stored.
+    pub fn contains_certificate(&self, certificate_id: &Field<N>) -> bool {
+        self.certificates.read().contains_key(certificate_id)
+    }
+
+    /// Returns the certificate for the given certificate ID, if it has been stored.
+    pub fn get_certificate(&self, certificate_id: &Field<N>) -> Option<BatchCertificate<N>> {
+        self.certificates.read().get(certificate_id).cloned()
+    }
+
+    /// Stores a batch certificate received (and verified) from a peer, whether fetched in
+    /// response to a request or broadcast as a `BatchSealed` event.
+    pub fn store_sealed_batch(&self, _peer_ip: SocketAddr, certificate: BatchCertificate<N>) {
+        self.insert_certificate(certificate);
+    }
+
+    /// Stores the batch certificate this primary just sealed locally.
+    pub fn store_sealed_batch_from_primary(&self, _address: Address<N>, sealed_batch: SealedBatch<N>) {
+        self.insert_certificate(sealed_batch.certificate().clone());
+    }
+
+    /// Inserts the given certificate into the certificate store, and — if it was sealed for
+    /// the current round — counts it towards this round's advancement quorum.
+    fn insert_certificate(&self, certificate: BatchCertificate<N>) {
+        let certificate_id = certificate.certificate_id();
+        if certificate.round() == self.round() {
+            self.current_round_certificates.write().insert(certificate_id, certificate.author());
+        }
+        self.certificates.write().insert(certificate_id, certificate);
+    }
+
+    /// If the authors of the certificates sealed so far for the current round — including the
+    /// certificate this primary sealed locally — form a `2f+1` stake-weighted quorum, advances
+    /// to the next round, snapshots those certificate IDs as the `previous_certificates` for
+    /// the next round's proposal, and returns the new round. Otherwise, returns `None`.
+    ///
+    /// Advancing clears the current round's certificate set, so a later call cannot satisfy
+    /// the same quorum a second time; the round only advances once per quorum reached.
+    pub fn advance_round_if_ready(&self) -> Option<u64> {
+        let sealed = self.current_round_certificates.read().clone();
+        let signers: Vec<_> = sealed.values().copied().collect();
+
+        if !self.is_quorum_threshold_reached(&signers) {
+            return None;
+        }
+
+        let next_round = self.round() + 1;
+        *self.round.write() = next_round;
+        self.previous_certificates.write().insert(next_round, sealed.keys().copied().collect());
+        self.current_round_certificates.write().clear();
+        Some(next_round)
+    }
+
+    /// Records that a certificate with the given ID and author was sealed for the current
+    /// round, without going through the full certificate store. Used only by tests, so that
+    /// round-advancement logic can be exercised without constructing a real `BatchCertificate`.
+    #[cfg(test)]
+    fn seal_for_current_round_for_test(&self, certificate_id: Field<N>, author: Address<N>) {
+        self.current_round_certificates.write().insert(certificate_id, author);
+    }
+
+    /// Returns `true` if the accumulated stake of the given signers reaches the `2f+1`
+    /// availability threshold of the committee, where `f` is derived from the total committee
+    /// stake: `threshold = floor(2 * total_stake / 3) + 1`.
+    ///
+    /// Duplicate entries in `signers` are only counted once, so a single validator cannot
+    /// inflate the quorum by being counted more than once.
+    pub fn is_quorum_threshold_reached(&self, signers: &[Address<N>]) -> bool {
+        let committee = self.committee.read();
+        let total_stake: u64 = committee.values().sum();
+        let threshold = total_stake * 2 / 3 + 1;
+
+        let mut counted = HashSet::new();
+        let stake: u64 = signers
+            .iter()
+            .filter(|address| counted.insert(**address))
+            .filter_map(|address| committee.get(address).copied())
+            .sum();
+
+        stake >= threshold
+    }
+
+    /// Returns `true` if the given certificate IDs are all stored, were all sealed for the
+    /// given `round`, and their authors form a valid `2f+1` stake-weighted quorum.
+    pub fn is_quorum_for_round(&self, certificate_ids: &IndexSet<Field<N>>, round: u64) -> bool {
+        let certificates = self.certificates.read();
+        let mut signers = Vec::with_capacity(certificate_ids.len());
+        for certificate_id in certificate_ids {
+            // A certificate we have not verified and stored cannot be counted towards quorum.
+            let Some(certificate) = certificates.get(certificate_id) else {
+                return false;
+            };
+            if certificate.round() != round {
+                return false;
+            }
+            signers.push(certificate.author());
+        }
+        drop(certificates);
+
+        self.is_quorum_threshold_reached(&signers)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use snarkos_account::Account;
+    use snarkvm::{console::network::Testnet3, utilities::TestRng};
+
+    type CurrentNetwork = Testnet3;
+
+    fn sample_address(rng: &mut TestRng) -> Address<CurrentNetwork> {
+        Account::<CurrentNetwork>::new(rng).unwrap().address()
+    }
+
+    #[test]
+    fn quorum_threshold_is_met_at_exactly_two_f_plus_one_but_not_one_short() {
+        let rng = &mut TestRng::default();
+        // Three equally-staked members, total stake 3, so the threshold is floor(2*3/3)+1 = 3.
+        let a = sample_address(rng);
+        let b = sample_address(rng);
+        let c = sample_address(rng);
+        let mut committee = IndexMap::new();
+        committee.insert(a, 1);
+        committee.insert(b, 1);
+        committee.insert(c, 1);
+        let shared = Shared::<CurrentNetwork>::new(committee);
+
+        // All three signers meet the threshold exactly.
+        assert!(shared.is_quorum_threshold_reached(&[a, b, c]));
+        // Any two signers are one unit of stake short of the threshold.
+        assert!(!shared.is_quorum_threshold_reached(&[a, b]));
+    }
+
+    #[test]
+    fn quorum_threshold_does_not_double_count_a_repeated_signer() {
+        let rng = &mut TestRng::default();
+        // Total stake 5, so the threshold is floor(2*5/3)+1 = 4.
+        let a = sample_address(rng);
+        let b = sample_address(rng);
+        let mut committee = IndexMap::new();
+        committee.insert(a, 3);
+        committee.insert(b, 1);
+        let shared = Shared::<CurrentNetwork>::new(committee);
+
+        // `a`'s stake alone (3) is below the threshold (4), even if `a` is listed twice; a
+        // buggy implementation that double-counts a repeated signer would reach 6 and wrongly
+        // report the quorum as met.
+        assert!(!shared.is_quorum_threshold_reached(&[a, a]));
+        // `a` and `b` together (4) meet the threshold.
+        assert!(shared.is_quorum_threshold_reached(&[a, b]));
+    }
+
+    #[test]
+    fn round_advances_once_per_quorum_and_does_not_refire() {
+        let rng = &mut TestRng::default();
+        // Three equally-staked members, total stake 3, so the threshold is floor(2*3/3)+1 = 3.
+        let a = sample_address(rng);
+        let b = sample_address(rng);
+        let c = sample_address(rng);
+        let mut committee = IndexMap::new();
+        committee.insert(a, 1);
+        committee.insert(b, 1);
+        committee.insert(c, 1);
+        let shared = Shared::<CurrentNetwork>::new(committee);
+
+        // Only two of the three authors have sealed a certificate so far: one unit of stake
+        // short of the threshold, so the round must not advance yet.
+        shared.seal_for_current_round_for_test(Field::rand(rng), a);
+        shared.seal_for_current_round_for_test(Field::rand(rng), b);
+        assert_eq!(shared.advance_round_if_ready(), None);
+        assert_eq!(shared.round(), 0);
+
+        // The third author's certificate completes the quorum; the round advances exactly once.
+        shared.seal_for_current_round_for_test(Field::rand(rng), c);
+        assert_eq!(shared.advance_round_if_ready(), Some(1));
+        assert_eq!(shared.round(), 1);
+
+        // Calling it again without any new certificates sealed for the new round must not
+        // re-fire, since the current round's certificate set was cleared on advancement.
+        assert_eq!(shared.advance_round_if_ready(), None);
+        assert_eq!(shared.round(), 1);
+    }
+}